@@ -0,0 +1,36 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Relative byte frequencies for typical input, used to build a fixed Huffman
+/// tree for single-pass streaming when the input cannot be scanned twice. Every
+/// entry is at least 1 so that every byte value has a code. The weights are
+/// approximate and only affect compression ratio, never correctness.
+pub(crate) static BYTE_FREQUENCIES: [u8; 256] = [
+    60, 1, 1, 1, 1, 1, 1, 1, 1, 20, 40, 1, 1, 8, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    255, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12,
+    30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 12, 12, 12, 12, 12, 12,
+    12, 54, 9, 18, 25, 85, 15, 13, 30, 47, 1, 5, 26, 16, 46, 50,
+    12, 1, 42, 44, 60, 17, 7, 14, 1, 13, 1, 12, 12, 12, 1, 12,
+    1, 162, 29, 55, 77, 255, 45, 40, 92, 142, 4, 17, 80, 48, 140, 151,
+    38, 3, 128, 133, 182, 53, 21, 44, 5, 39, 2, 12, 1, 12, 1, 1,
+    8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 40,
+];
@@ -12,10 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod byte_frequencies;
 mod util;
 
-use std::cmp;
-use std::collections::BinaryHeap;
+use byte_frequencies::BYTE_FREQUENCIES;
+
+use core::cmp;
+use core::fmt;
+
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::error;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
 use std::io::prelude::*;
 
 use bv::BitVec;
@@ -24,6 +42,46 @@ use bv::Bits;
 use util::BitIter;
 use util::Keyed;
 
+/// A sink for decoded or encoded bytes. This abstracts over the output target
+/// so the core codec does not depend on `std::io`; it is implemented for
+/// `Vec<u8>` and, with the `std` feature, for any [`std::io::Write`].
+pub trait ByteSink {
+    /// Append a single byte.
+    fn push_byte(&mut self, byte: u8);
+
+    /// Append a slice of bytes. Defaults to repeated [`ByteSink::push_byte`]
+    /// but implementations may override it for efficiency.
+    fn extend(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.push_byte(byte);
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl ByteSink for Vec<u8> {
+    fn push_byte(&mut self, byte: u8) {
+        self.push(byte);
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+// With `std`, `Vec<u8>` already implements `Write`, so the blanket impl below
+// covers it too; a separate `Vec` impl would conflict.
+#[cfg(feature = "std")]
+impl<W: Write> ByteSink for W {
+    fn push_byte(&mut self, byte: u8) {
+        self.write_all(core::slice::from_ref(&byte)).unwrap();
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.write_all(bytes).unwrap();
+    }
+}
+
 /// Stores the probability (in the form of a raw count) of each possible input
 /// symbol. In this case, a symbol is any byte.
 #[derive(Clone, Copy)]
@@ -44,9 +102,9 @@ pub struct SymbolCode {
 }
 
 /// Encode the input with a precomputed Huffman code
-pub fn encode<I: IntoIterator<Item = u8>, W: Write>(
+pub fn encode<I: IntoIterator<Item = u8>, S: ByteSink>(
     input: I,
-    output: &mut W,
+    output: &mut S,
     symbol_codes: &SymbolCodes,
 ) {
     let codes = symbol_codes.codes;
@@ -61,7 +119,7 @@ pub fn encode<I: IntoIterator<Item = u8>, W: Write>(
         let full_bytes = accumulator.bit_len() / 8;
         for i in 0..full_bytes {
             let block = accumulator.get_block(i as usize);
-            output.write(std::slice::from_ref(&block)).unwrap();
+            output.push_byte(block);
         }
 
         let remainder_len = accumulator.bit_len() - full_bytes * 8;
@@ -75,12 +133,23 @@ pub fn encode<I: IntoIterator<Item = u8>, W: Write>(
             accumulator.clear();
         }
     }
+
+    // Flush any leftover bits as a final zero-padded byte. [`decode_fast`] is
+    // bounded by the original symbol count, so it never decodes the padding
+    // bits; [`decode`] has no such bound and may decode them as extra symbols.
+    if accumulator.bit_len() > 0 {
+        output.push_byte(accumulator.get_block(0));
+    }
 }
 
-pub fn decode<I: IntoIterator<Item = u8>, W: Write>(input: I, output: &mut W, tree: &HuffNode) {
+pub fn decode<I: IntoIterator<Item = u8>, S: ByteSink>(
+    input: I,
+    output: &mut S,
+    tree: &HuffNode,
+) {
     let mut iter = BitIter::new(input.into_iter());
     while let Some(symbol) = decode_symbol(&mut iter, tree) {
-        output.write(std::slice::from_ref(&symbol)).unwrap();
+        output.push_byte(symbol);
     }
 }
 
@@ -97,6 +166,202 @@ fn decode_symbol<I: Iterator<Item = bool>>(bits: &mut I, tree: &HuffNode) -> Opt
     }
 }
 
+/// A lookup table that decodes several bits per step instead of walking
+/// `HuffNode` one bit at a time. Each level resolves `level_bits` bits at once;
+/// codes longer than that chain through linked sub-tables, so every table holds
+/// exactly `2^level_bits` entries and total memory stays `O(symbols * depth)`
+/// regardless of the maximum code length (unlike a single `2^max_len` table).
+pub struct DecodeTable {
+    level_bits: u32,
+    depth: u32,
+    tables: Vec<Vec<TableEntry>>,
+}
+
+#[derive(Clone, Copy)]
+enum TableEntry {
+    Empty,
+    Symbol { symbol: u8, len: u8 },
+    Link { table: u32 },
+}
+
+/// Maximum number of bits resolved per table level; tables for shorter codes
+/// use levels only as wide as the longest code.
+const ROOT_BITS: u32 = 8;
+
+impl DecodeTable {
+    /// Build a decode table from a set of canonical codes.
+    pub fn new(symbol_codes: &SymbolCodes) -> DecodeTable {
+        let max_len = symbol_codes
+            .codes
+            .iter()
+            .map(|c| c.bit_len() as u32)
+            .max()
+            .unwrap_or(0);
+        let level_bits = max_len.clamp(1, ROOT_BITS);
+        let depth = cmp::max(1, max_len.div_ceil(level_bits));
+
+        let mut tables: Vec<Vec<TableEntry>> = vec![vec![TableEntry::Empty; 1 << level_bits]];
+
+        for symbol in 0..256 {
+            let code = &symbol_codes.codes[symbol];
+            let len = code.bit_len() as u32;
+            if len > 0 {
+                add_code(
+                    &mut tables,
+                    level_bits,
+                    depth,
+                    code_value(code),
+                    len,
+                    symbol as u8,
+                );
+            }
+        }
+
+        DecodeTable {
+            level_bits: level_bits,
+            depth: depth,
+            tables: tables,
+        }
+    }
+
+    /// Bit width of a left-justified peek, a whole number of levels wide.
+    fn peek_bits(&self) -> u32 {
+        self.depth * self.level_bits
+    }
+
+    /// Resolve a left-justified `peek` of [`DecodeTable::peek_bits`] bits (real
+    /// bits at the top, zero-padded below) to a `(symbol, len)` pair, or `None`
+    /// when the slot names no code.
+    fn lookup(&self, peek: u64) -> Option<(u8, u8)> {
+        let r = self.level_bits;
+        let mask = (1u64 << r) - 1;
+        let mut table = 0usize;
+        for level in 0..self.depth {
+            let shift = (self.depth - level - 1) * r;
+            let index = ((peek >> shift) & mask) as usize;
+            match self.tables[table][index] {
+                TableEntry::Symbol { symbol, len } => return Some((symbol, len)),
+                TableEntry::Link { table: next } => table = next as usize,
+                TableEntry::Empty => return None,
+            }
+        }
+        None
+    }
+}
+
+/// Insert one canonical code into the multi-level table, creating linked
+/// sub-tables for the bits that overflow a single level.
+fn add_code(
+    tables: &mut Vec<Vec<TableEntry>>,
+    r: u32,
+    depth: u32,
+    value: u32,
+    len: u32,
+    symbol: u8,
+) {
+    // Left-justify the code within the full `depth * r`-bit key so each level
+    // peels off its `r` bits from the top.
+    let key = (value as u64) << (depth * r - len);
+    let mask = (1u64 << r) - 1;
+
+    let mut table = 0usize;
+    let mut level = 0u32;
+    loop {
+        let shift = (depth - level - 1) * r;
+        let index = ((key >> shift) & mask) as usize;
+        if len <= (level + 1) * r {
+            // Final level: the real code occupies the top `level_code_bits` of
+            // this slot; fill every index sharing that prefix.
+            let level_code_bits = len - level * r;
+            let dont_care = r - level_code_bits;
+            let base = (index >> dont_care) << dont_care;
+            let entry = TableEntry::Symbol {
+                symbol: symbol,
+                len: len as u8,
+            };
+            for slot in &mut tables[table][base..base + (1 << dont_care)] {
+                *slot = entry;
+            }
+            return;
+        }
+
+        let next = match tables[table][index] {
+            TableEntry::Link { table } => table as usize,
+            _ => {
+                let new_index = tables.len();
+                tables.push(vec![TableEntry::Empty; 1 << r]);
+                tables[table][index] = TableEntry::Link {
+                    table: new_index as u32,
+                };
+                new_index
+            }
+        };
+        table = next;
+        level += 1;
+    }
+}
+
+/// Read a canonical code back as an integer, most-significant bit first.
+fn code_value(code: &SymbolCode) -> u32 {
+    let mut value = 0u32;
+    for i in 0..code.bit_len() {
+        value = (value << 1) | (code.get_bit(i) as u32);
+    }
+    value
+}
+
+/// Decode exactly `count` symbols from a bitstream with a [`DecodeTable`],
+/// consuming several bits per step. This is the fast counterpart to [`decode`],
+/// which walks the tree a single bit at a time. Bounding by the known symbol
+/// count (as the container stores it) means trailing zero padding is never
+/// mistaken for real symbols. Decoding stops early if the bits run out, so
+/// callers can detect truncation by comparing how much was written.
+pub fn decode_fast<I: IntoIterator<Item = u8>, S: ByteSink>(
+    input: I,
+    output: &mut S,
+    table: &DecodeTable,
+    count: u64,
+) {
+    debug_assert!(table.peek_bits() <= 64, "max code length too large for u64 buffer");
+    let mut iter = input.into_iter();
+    let w = table.peek_bits();
+
+    // `buffer` holds pending bits left-aligned (next bit in the most
+    // significant position); `nbits` counts how many of them are real. Each
+    // byte is bit-reversed before packing because `encode` writes bits
+    // LSB-first (through `bv::BitVec`, matching `BitIter`), so the first bit of
+    // the stream is bit 0 of the byte and must land at the top of the window.
+    let mut buffer: u64 = 0;
+    let mut nbits: u32 = 0;
+
+    for _ in 0..count {
+        while nbits + 8 <= 64 && nbits < w {
+            match iter.next() {
+                Some(byte) => {
+                    buffer |= (byte.reverse_bits() as u64) << (64 - 8 - nbits);
+                    nbits += 8;
+                }
+                None => break,
+            }
+        }
+        if nbits == 0 {
+            break;
+        }
+
+        let peek = buffer >> (64 - w);
+        match table.lookup(peek) {
+            Some((symbol, len)) if (len as u32) <= nbits => {
+                output.push_byte(symbol);
+                buffer <<= len;
+                nbits -= len as u32;
+            }
+            // The slot is empty or the matched code runs past the real bits
+            // that remain: the stream is truncated, so stop.
+            _ => break,
+        }
+    }
+}
+
 pub fn codes_from_tree(tree: &HuffNode) -> SymbolCodes {
     let mut codes = [Default::default(); 256];
 
@@ -156,6 +421,153 @@ impl Bits for SymbolCode {
     }
 }
 
+/// Returned when the requested maximum code length is too small to code the
+/// given number of distinct symbols at all (`max_len` must be at least
+/// `ceil(log2(n))`, and at least 1).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthLimitError {
+    /// Smallest `max_len` that could possibly work for this input.
+    pub needed: u32,
+    /// The `max_len` that was supplied.
+    pub given: u32,
+}
+
+impl fmt::Display for LengthLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "max code length {} is too small; need at least {}",
+            self.given, self.needed
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for LengthLimitError {}
+
+/// A coin in the package-merge algorithm: a weight together with the multiset
+/// of original symbols it is built from. Original coins cover a single symbol;
+/// packages cover the union of their two halves.
+#[derive(Clone)]
+struct Package {
+    weight: u64,
+    symbols: Vec<u8>,
+}
+
+/// Build length-limited canonical code lengths with the package-merge
+/// algorithm. Unlike [`build_tree`], no code is longer than `max_len` bits,
+/// which keeps table-based decoders bounded. Returns an error if `max_len` is
+/// smaller than `ceil(log2(n))` or than 1, where `n` is the number of distinct
+/// symbols.
+pub fn build_tree_limited(
+    symbol_counts: &SymbolCounts,
+    max_len: u32,
+) -> Result<[u8; 256], LengthLimitError> {
+    let counts = symbol_counts.counts;
+
+    let mut symbols: Vec<Package> = Vec::new();
+    for symbol in 0..256 {
+        if counts[symbol] > 0 {
+            symbols.push(Package {
+                weight: counts[symbol] as u64,
+                symbols: vec![symbol as u8],
+            });
+        }
+    }
+    symbols.sort_by_key(|p| p.weight);
+
+    let n = symbols.len();
+    let needed = cmp::max(1, ceil_log2(n as u32));
+    if max_len < needed {
+        return Err(LengthLimitError {
+            needed: needed,
+            given: max_len,
+        });
+    }
+
+    let mut lengths = [0u8; 256];
+
+    // No symbols (empty input) means no codes at all.
+    if n == 0 {
+        return Ok(lengths);
+    }
+
+    // A single distinct symbol needs one bit; package-merge degenerates here
+    // and `build_tree` would panic on `node_queue.len() > 1`, so handle it up
+    // front.
+    if n == 1 {
+        lengths[symbols[0].symbols[0] as usize] = 1;
+        return Ok(lengths);
+    }
+
+    // M_1 is just the sorted symbols; each later M_k pairs the previous list
+    // into packages and merges them back with the original coins.
+    let mut merged: Vec<Package> = Vec::new();
+    for _ in 0..max_len {
+        let mut packages: Vec<Package> = Vec::with_capacity(merged.len() / 2);
+        let mut i = 0;
+        while i + 1 < merged.len() {
+            let mut combined = merged[i].symbols.clone();
+            combined.extend_from_slice(&merged[i + 1].symbols);
+            packages.push(Package {
+                weight: merged[i].weight + merged[i + 1].weight,
+                symbols: combined,
+            });
+            i += 2;
+        }
+        merged = merge_packages(&symbols, &packages);
+    }
+
+    // The 2n-2 cheapest items of the final list select which symbols gain a
+    // bit; a symbol's code length is how many selected items cover it.
+    let take = 2 * n - 2;
+    for package in &merged[..take] {
+        for &symbol in &package.symbols {
+            lengths[symbol as usize] += 1;
+        }
+    }
+
+    Ok(lengths)
+}
+
+/// Merge two ascending-by-weight package lists into one ascending list.
+fn merge_packages(a: &[Package], b: &[Package]) -> Vec<Package> {
+    let mut result = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].weight <= b[j].weight {
+            result.push(a[i].clone());
+            i += 1;
+        } else {
+            result.push(b[j].clone());
+            j += 1;
+        }
+    }
+    result.extend_from_slice(&a[i..]);
+    result.extend_from_slice(&b[j..]);
+    result
+}
+
+/// Smallest number of bits `b` such that `2^b >= n`.
+fn ceil_log2(n: u32) -> u32 {
+    if n <= 1 {
+        0
+    } else {
+        32 - (n - 1).leading_zeros()
+    }
+}
+
+/// Build a fixed Huffman tree from the static [`BYTE_FREQUENCIES`] table. Every
+/// byte value is present, so this tree can encode any input without first
+/// scanning it — the basis for single-pass streaming compression.
+pub fn build_tree_default() -> HuffNode {
+    let mut counts = [0u32; 256];
+    for symbol in 0..256 {
+        counts[symbol] = BYTE_FREQUENCIES[symbol] as u32;
+    }
+    build_tree(&SymbolCounts { counts: counts })
+}
+
 pub fn build_tree(symbol_counts: &SymbolCounts) -> HuffNode {
     let counts = symbol_counts.counts;
 
@@ -217,3 +629,316 @@ pub fn count_symbols<I: IntoIterator<Item = u8>>(input: I) -> SymbolCounts {
 
     SymbolCounts { counts: counts }
 }
+
+/// Walk `tree` and record the depth (code length) of each symbol's leaf. A
+/// length of 0 means the symbol does not appear in the tree.
+pub fn code_lengths_from_tree(tree: &HuffNode) -> [u8; 256] {
+    let mut lengths = [0u8; 256];
+    code_lengths_impl(tree, 0, &mut lengths);
+    lengths
+}
+
+fn code_lengths_impl(tree: &HuffNode, depth: u8, lengths: &mut [u8; 256]) {
+    match tree {
+        HuffNode::Leaf(l) => lengths[l.symbol as usize] = depth,
+        HuffNode::Parent(p) => {
+            code_lengths_impl(&p.zero, depth + 1, lengths);
+            code_lengths_impl(&p.one, depth + 1, lengths);
+        }
+    }
+}
+
+/// Assign canonical Huffman codes given the code length of each symbol. Symbols
+/// are numbered in `(length, symbol)` order: shorter codes come first, ties
+/// broken by byte value. Symbols with length 0 are absent and left empty.
+pub fn canonical_codes(lengths: &[u8; 256]) -> SymbolCodes {
+    let max_len = lengths.iter().copied().max().unwrap_or(0) as usize;
+
+    let mut bl_count = vec![0u32; max_len + 1];
+    for &len in lengths.iter() {
+        if len > 0 {
+            bl_count[len as usize] += 1;
+        }
+    }
+
+    let mut next_code = vec![0u32; max_len + 1];
+    let mut code = 0u32;
+    for bits in 1..=max_len {
+        code = (code + bl_count[bits - 1]) << 1;
+        next_code[bits] = code;
+    }
+
+    let mut codes = [Default::default(); 256];
+    for symbol in 0..256 {
+        let len = lengths[symbol] as usize;
+        if len > 0 {
+            codes[symbol] = canonical_symbol_code(next_code[len], len);
+            next_code[len] += 1;
+        }
+    }
+
+    SymbolCodes { codes: codes }
+}
+
+/// Build a `SymbolCode` from a canonical code value emitted most-significant
+/// bit first, matching the bit order that `encode` writes and `decode` reads.
+fn canonical_symbol_code(code: u32, len: usize) -> SymbolCode {
+    let mut bits = BitVec::<u8>::new();
+    for i in (0..len).rev() {
+        bits.push((code >> i) & 1 == 1);
+    }
+    SymbolCode::from_bits(&bits)
+}
+
+/// Reconstruct a decoding tree from a set of canonical codes. Each present
+/// symbol's bit string traces a path from the root, creating interior nodes as
+/// needed; the leaf at the end of the path holds the symbol.
+pub fn tree_from_codes(symbol_codes: &SymbolCodes) -> HuffNode {
+    let mut root = TreeBuilder::new_inner();
+    for symbol in 0..256 {
+        let code = &symbol_codes.codes[symbol];
+        if code.bit_len() == 0 {
+            continue;
+        }
+        let mut cur = &mut root;
+        for i in 0..code.bit_len() {
+            cur = cur.child(code.get_bit(i));
+        }
+        *cur = TreeBuilder::Leaf(symbol as u8);
+    }
+    root.finish()
+}
+
+/// Mutable scaffolding used while rebuilding a tree one code at a time. It is
+/// collapsed into an immutable `HuffNode` by [`TreeBuilder::finish`].
+enum TreeBuilder {
+    Empty,
+    Leaf(u8),
+    Inner(Box<TreeBuilder>, Box<TreeBuilder>),
+}
+
+impl TreeBuilder {
+    fn new_inner() -> TreeBuilder {
+        TreeBuilder::Inner(Box::new(TreeBuilder::Empty), Box::new(TreeBuilder::Empty))
+    }
+
+    fn child(&mut self, one: bool) -> &mut TreeBuilder {
+        if let TreeBuilder::Empty = self {
+            *self = TreeBuilder::new_inner();
+        }
+        match self {
+            TreeBuilder::Inner(zero, one_node) => {
+                if one {
+                    one_node
+                } else {
+                    zero
+                }
+            }
+            _ => panic!("code is a prefix of another code"),
+        }
+    }
+
+    fn finish(self) -> HuffNode {
+        match self {
+            TreeBuilder::Leaf(symbol) => HuffNode::Leaf(HuffLeaf { symbol: symbol }),
+            TreeBuilder::Inner(zero, one) => HuffNode::Parent(HuffParent {
+                zero: Box::new(zero.finish()),
+                one: Box::new(one.finish()),
+            }),
+            TreeBuilder::Empty => panic!("incomplete tree: dangling branch"),
+        }
+    }
+}
+
+/// Set when the header carries a trailing BLAKE3 digest of the original input.
+#[cfg(feature = "std")]
+const FLAG_HAS_DIGEST: u8 = 0b0000_0001;
+
+/// Set when the stream was encoded with the fixed [`build_tree_default`] tree
+/// rather than one tailored to the input. The per-symbol length table is then
+/// omitted from the header, since the decoder rebuilds the same fixed tree.
+#[cfg(feature = "std")]
+const FLAG_FIXED_TREE: u8 = 0b0000_0010;
+
+/// Upper bound on container code lengths. Keeping it below 32 means canonical
+/// code values fit in the `u32` accumulator, and the decode table stays small.
+/// It is comfortably above `ceil(log2(256)) = 8`, so construction never fails.
+#[cfg(feature = "std")]
+const MAX_CODE_LEN: u32 = 30;
+
+/// Failure modes when reading a compressed container. `Io` covers truncated or
+/// otherwise malformed input; `IntegrityMismatch` means the stream parsed fine
+/// but its BLAKE3 digest did not match the decoded output — i.e. corruption.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    IntegrityMismatch,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Io(e) => write!(f, "malformed compressed stream: {}", e),
+            DecodeError::IntegrityMismatch => {
+                write!(f, "integrity check failed: decoded data does not match digest")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DecodeError::Io(e) => Some(e),
+            DecodeError::IntegrityMismatch => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> DecodeError {
+        DecodeError::Io(e)
+    }
+}
+
+/// Compress `input` into `output` using a self-describing container. The header
+/// records the original byte length, a flag byte, an optional BLAKE3 digest of
+/// the input, and the canonical code length of every symbol, which is all a
+/// reader needs to rebuild the tree before decoding the bitstream that follows.
+///
+/// When `verify` is set the input's BLAKE3 digest is stored so a later
+/// [`read_compressed`] can detect corruption end to end.
+#[cfg(feature = "std")]
+pub fn write_compressed<W: Write>(input: &[u8], output: &mut W, verify: bool) -> io::Result<()> {
+    let counts = count_symbols(input.iter().copied());
+    // `build_tree_limited` handles the single-symbol and empty inputs that make
+    // `build_tree` panic, and bounds code lengths so canonical codes fit in the
+    // `u32` accumulator.
+    let lengths = build_tree_limited(&counts, MAX_CODE_LEN)
+        .expect("MAX_CODE_LEN always suffices for at most 256 symbols");
+
+    output.write_all(&(input.len() as u64).to_le_bytes())?;
+    let flags = if verify { FLAG_HAS_DIGEST } else { 0 };
+    output.write_all(&[flags])?;
+    if verify {
+        output.write_all(blake3::hash(input).as_bytes())?;
+    }
+    output.write_all(&lengths)?;
+
+    if !input.is_empty() {
+        let codes = canonical_codes(&lengths);
+        encode(input.iter().copied(), output, &codes);
+    }
+    Ok(())
+}
+
+/// Compress `input` in a single pass using the fixed [`build_tree_default`]
+/// tree, for sources that cannot be scanned twice (e.g. a pipe). Unlike
+/// [`write_compressed`], this never rewinds the input: the only pass counts the
+/// bytes, feeds the optional digest, and emits the bitstream at once. The
+/// bitstream is buffered so the byte count can be written into the header that
+/// precedes it; the input itself is consumed exactly once.
+#[cfg(feature = "std")]
+pub fn write_compressed_streaming<I: IntoIterator<Item = u8>, W: Write>(
+    input: I,
+    output: &mut W,
+    verify: bool,
+) -> io::Result<()> {
+    let tree = build_tree_default();
+    let codes = codes_from_tree(&tree);
+
+    let mut hasher = blake3::Hasher::new();
+    let mut len = 0u64;
+    let mut encoded = Vec::new();
+
+    {
+        let teed = input.into_iter().map(|byte| {
+            len += 1;
+            if verify {
+                hasher.update(core::slice::from_ref(&byte));
+            }
+            byte
+        });
+        encode(teed, &mut encoded, &codes);
+    }
+
+    output.write_all(&len.to_le_bytes())?;
+    let mut flags = FLAG_FIXED_TREE;
+    if verify {
+        flags |= FLAG_HAS_DIGEST;
+    }
+    output.write_all(&[flags])?;
+    if verify {
+        output.write_all(hasher.finalize().as_bytes())?;
+    }
+    // No length table: the decoder rebuilds the fixed tree itself.
+    output.write_all(&encoded)?;
+    Ok(())
+}
+
+/// Decompress a container produced by [`write_compressed`], writing exactly the
+/// original number of bytes to `output`.
+///
+/// When `verify` is set and the stream carries a digest, the decoded output is
+/// hashed with BLAKE3 and compared against it; a mismatch yields
+/// [`DecodeError::IntegrityMismatch`]. A stream written without a digest is
+/// decoded without the check regardless of `verify`.
+#[cfg(feature = "std")]
+pub fn read_compressed<R: Read, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    verify: bool,
+) -> Result<(), DecodeError> {
+    let mut len_bytes = [0u8; 8];
+    input.read_exact(&mut len_bytes)?;
+    let original_len = u64::from_le_bytes(len_bytes);
+
+    let mut flags = [0u8; 1];
+    input.read_exact(&mut flags)?;
+    let has_digest = flags[0] & FLAG_HAS_DIGEST != 0;
+    let fixed_tree = flags[0] & FLAG_FIXED_TREE != 0;
+
+    let mut digest = [0u8; 32];
+    if has_digest {
+        input.read_exact(&mut digest)?;
+    }
+
+    let codes = if fixed_tree {
+        // The length table is omitted for the fixed tree; rebuild it directly.
+        codes_from_tree(&build_tree_default())
+    } else {
+        let mut lengths = [0u8; 256];
+        input.read_exact(&mut lengths)?;
+        canonical_codes(&lengths)
+    };
+    let table = DecodeTable::new(&codes);
+
+    // Decode straight into a buffer so the output length (which detects
+    // truncation) and the digest can both be checked before anything is
+    // committed to `output`.
+    let mut decoded = Vec::with_capacity(original_len as usize);
+    decode_fast(
+        input.bytes().map(Result::unwrap),
+        &mut decoded,
+        &table,
+        original_len,
+    );
+
+    if decoded.len() as u64 != original_len {
+        return Err(DecodeError::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "bitstream ended before all symbols were decoded",
+        )));
+    }
+
+    if verify && has_digest && blake3::hash(&decoded).as_bytes() != &digest {
+        return Err(DecodeError::IntegrityMismatch);
+    }
+
+    output.write_all(&decoded)?;
+    Ok(())
+}
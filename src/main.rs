@@ -16,67 +16,49 @@ use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
-use std::io::stdin;
-use std::io::stdout;
 use std::io::BufReader;
 use std::io::BufWriter;
-use std::io::SeekFrom;
 use std::vec::Vec;
 
-use bv::Bits;
-
-use huff::build_tree;
-use huff::codes_from_tree;
-use huff::count_symbols;
-use huff::decode;
-use huff::encode;
-use huff::SymbolCodes;
+use huff::read_compressed;
+use huff::write_compressed;
+use huff::write_compressed_streaming;
 
 fn main() -> io::Result<()> {
-    let args: Vec<_> = env::args().take(3).collect();
-    if args.len() < 3 {
+    let args: Vec<_> = env::args().take(4).collect();
+    if args.len() < 4 {
         let progname = if args.len() > 0 { &args[0] } else { "?" };
         println!("incorrect arguments");
-        println!("usage: {} <input-filename> <output-filename>", progname);
+        println!(
+            "usage: {} <c|s|d> <input-filename> <output-filename>",
+            progname
+        );
         return Ok(());
     }
 
-    let mut infile = File::open(&args[1])?;
-
-    let symbol_counts = count_symbols(BufReader::new(&infile).bytes().map(Result::unwrap));
-
-    let tree = build_tree(&symbol_counts);
-    let codes = codes_from_tree(&tree);
-    print_codes(&codes);
-
-    let mut encoded = Vec::new();
-    encoded.reserve(infile.metadata()?.len() as usize);
-
-    infile.seek(SeekFrom::Start(0))?;
-    encode(
-        BufReader::new(infile).bytes().map(Result::unwrap),
-        &mut encoded,
-        &codes,
-    );
-
-    let outfile = File::create(&args[2])?;
-    decode(encoded.iter().copied(), &mut BufWriter::new(outfile), &tree);
-
-    Ok(())
-}
+    let mut infile = BufReader::new(File::open(&args[2])?);
+    let mut outfile = BufWriter::new(File::create(&args[3])?);
 
-fn print_codes(symbol_codes: &SymbolCodes) {
-    for (sym, code) in (&symbol_codes.codes).iter().enumerate() {
-        if code.bit_len() > 0 {
-            println!("{}\t{}", sym as u8 as char, bits_to_string(code));
+    match args[1].as_str() {
+        "c" => {
+            let mut input = Vec::new();
+            infile.read_to_end(&mut input)?;
+            write_compressed(&input, &mut outfile, true)?;
+        }
+        "s" => {
+            // Single pass over the input, no rewind — works on pipes.
+            write_compressed_streaming(
+                infile.bytes().map(Result::unwrap),
+                &mut outfile,
+                true,
+            )?;
+        }
+        "d" => read_compressed(&mut infile, &mut outfile, true)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        other => {
+            println!("unknown mode {:?}; expected 'c', 's' or 'd'", other);
         }
     }
-}
 
-fn bits_to_string<B: Bits>(bits: B) -> String {
-    let mut result = String::new();
-    for i in 0..bits.bit_len() {
-        result.push(if bits.get_bit(i) { '1' } else { '0' });
-    }
-    result
+    Ok(())
 }
@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cmp;
-use std::iter::Iterator;
+use core::cmp;
+use core::iter::Iterator;
 
 use bv::Bits;
 
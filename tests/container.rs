@@ -0,0 +1,76 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use huff::read_compressed;
+use huff::write_compressed;
+use huff::DecodeError;
+
+fn round_trip(data: &[u8]) {
+    let mut compressed = Vec::new();
+    write_compressed(data, &mut compressed, false).unwrap();
+
+    let mut decompressed = Vec::new();
+    read_compressed(&mut &compressed[..], &mut decompressed, false).unwrap();
+
+    assert_eq!(decompressed, data);
+}
+
+#[test]
+fn round_trip_empty() {
+    round_trip(b"");
+}
+
+#[test]
+fn round_trip_single_symbol() {
+    round_trip(b"aaaaaaaa");
+}
+
+#[test]
+fn round_trip_general() {
+    round_trip(b"hello world");
+    round_trip(b"the quick brown fox jumps over the lazy dog");
+
+    let all_bytes: Vec<u8> = (0..=255).collect();
+    round_trip(&all_bytes);
+}
+
+#[test]
+fn round_trip_with_digest() {
+    let data = b"hello world";
+    let mut compressed = Vec::new();
+    write_compressed(data, &mut compressed, true).unwrap();
+
+    let mut decompressed = Vec::new();
+    read_compressed(&mut &compressed[..], &mut decompressed, true).unwrap();
+
+    assert_eq!(decompressed, data);
+}
+
+#[test]
+fn detects_corruption() {
+    let data = b"the quick brown fox jumps over the lazy dog";
+    let mut compressed = Vec::new();
+    write_compressed(data, &mut compressed, true).unwrap();
+
+    // Flip a bit in the stored 32-byte digest, which begins right after the
+    // 8-byte length and the 1-byte flags. Decoding still succeeds, but the
+    // recomputed digest no longer matches, so corruption is reported.
+    compressed[9] ^= 0x01;
+
+    let mut decompressed = Vec::new();
+    match read_compressed(&mut &compressed[..], &mut decompressed, true) {
+        Err(DecodeError::IntegrityMismatch) => {}
+        other => panic!("expected IntegrityMismatch, got {:?}", other),
+    }
+}
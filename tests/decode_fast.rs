@@ -0,0 +1,52 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use huff::build_tree_limited;
+use huff::canonical_codes;
+use huff::count_symbols;
+use huff::decode_fast;
+use huff::encode;
+use huff::DecodeTable;
+
+/// The table decoder must consume bits in the same order `encode` writes them.
+fn assert_fast_round_trip(data: &[u8]) {
+    let counts = count_symbols(data.iter().copied());
+    let lengths = build_tree_limited(&counts, 30).unwrap();
+    let codes = canonical_codes(&lengths);
+
+    let mut encoded = Vec::new();
+    encode(data.iter().copied(), &mut encoded, &codes);
+
+    let table = DecodeTable::new(&codes);
+    let mut decoded = Vec::new();
+    decode_fast(
+        encoded.iter().copied(),
+        &mut decoded,
+        &table,
+        data.len() as u64,
+    );
+
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn decode_fast_matches_encode() {
+    assert_fast_round_trip(b"hello world");
+}
+
+#[test]
+fn decode_fast_handles_all_bytes() {
+    let all: Vec<u8> = (0..=255).collect();
+    assert_fast_round_trip(&all);
+}
@@ -0,0 +1,45 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use huff::read_compressed;
+use huff::write_compressed_streaming;
+
+#[test]
+fn streaming_round_trip() {
+    let data: Vec<u8> = b"hello world "
+        .iter()
+        .copied()
+        .cycle()
+        .take(100_000)
+        .collect();
+
+    let mut compressed = Vec::new();
+    write_compressed_streaming(data.iter().copied(), &mut compressed, true).unwrap();
+
+    let mut decompressed = Vec::new();
+    read_compressed(&mut &compressed[..], &mut decompressed, true).unwrap();
+
+    assert_eq!(decompressed, data);
+}
+
+#[test]
+fn streaming_empty() {
+    let mut compressed = Vec::new();
+    write_compressed_streaming(std::iter::empty(), &mut compressed, false).unwrap();
+
+    let mut decompressed = Vec::new();
+    read_compressed(&mut &compressed[..], &mut decompressed, false).unwrap();
+
+    assert!(decompressed.is_empty());
+}